@@ -1,9 +1,11 @@
 use anyhow::anyhow;
-use std::sync::mpsc;
+use concurrency::bounded::{self, BoundedSender};
 use std::thread;
 use std::time::Duration;
 
 const NUM_PRODUCERS: usize = 4;
+const NUM_CONSUMERS: usize = 2;
+const CHANNEL_CAPACITY: usize = 16;
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -13,33 +15,42 @@ struct Msg {
 }
 
 fn main() -> anyhow::Result<()> {
-    let (tx, rx) = mpsc::channel();
+    let (tx, rx) = bounded::channel(CHANNEL_CAPACITY);
 
     for i in 0..NUM_PRODUCERS {
         let tx = tx.clone(); // 下面使用的全是 clone 出来的tx，原始的 tx 没人用
         thread::spawn(move || produce(i, tx));
     }
-
-    let consumer = thread::spawn(move || {
-        for msg in rx {
-            println!("consumer: {:?}", msg);
-        }
-        println!("consumer exit.");
-        42
-    });
     drop(tx); // 释放原始的 tx， 否则 rx 无法结束
 
-    let secret = consumer
-        .join()
-        .map_err(|e| anyhow!("Thread join error: {:?}", e))?;
-    println!("secret: {secret}");
+    let consumers = (0..NUM_CONSUMERS)
+        .map(|id| {
+            let rx = rx.clone();
+            thread::spawn(move || {
+                while let Ok(msg) = rx.recv() {
+                    println!("consumer {id}: {:?}", msg);
+                }
+                println!("consumer {id} exit.");
+                42
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for consumer in consumers {
+        let secret = consumer
+            .join()
+            .map_err(|e| anyhow!("Thread join error: {:?}", e))?;
+        println!("secret: {secret}");
+    }
     Ok(())
 }
 
-fn produce(idx: usize, tx: mpsc::Sender<Msg>) -> anyhow::Result<()> {
+fn produce(idx: usize, tx: BoundedSender<Msg>) -> anyhow::Result<()> {
     loop {
         let value = rand::random::<usize>();
-        tx.send(Msg::new(idx, value))?;
+        if tx.send(Msg::new(idx, value)).is_err() {
+            break; // every consumer has dropped
+        }
         let sleep_time = rand::random::<u8>() as u64 * 10;
         thread::sleep(Duration::from_millis(sleep_time));
 
@@ -0,0 +1,248 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+
+struct Shared<T> {
+    queue: Mutex<VecDeque<T>>,
+    not_full: Condvar,
+    not_empty: Condvar,
+    capacity: usize,
+    senders: AtomicUsize,
+    receivers: AtomicUsize,
+}
+
+pub struct BoundedSender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+pub struct BoundedReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Mirrors `mpsc::RecvError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Disconnected;
+
+impl fmt::Display for Disconnected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receiving on an empty and disconnected channel")
+    }
+}
+
+impl std::error::Error for Disconnected {}
+
+/// Hands back the value once every receiver has dropped.
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sending on a disconnected channel")
+    }
+}
+
+impl<T> std::error::Error for SendError<T> {}
+
+/// Hands back the value: the queue was full, or every receiver dropped.
+pub enum TrySendError<T> {
+    Full(T),
+    Disconnected(T),
+}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "TrySendError::Full(..)"),
+            TrySendError::Disconnected(_) => write!(f, "TrySendError::Disconnected(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "sending on a full channel"),
+            TrySendError::Disconnected(_) => write!(f, "sending on a disconnected channel"),
+        }
+    }
+}
+
+impl<T> std::error::Error for TrySendError<T> {}
+
+/// A channel bounded to `capacity` items, giving producers backpressure.
+pub fn channel<T>(capacity: usize) -> (BoundedSender<T>, BoundedReceiver<T>) {
+    let shared = Arc::new(Shared {
+        queue: Mutex::new(VecDeque::with_capacity(capacity)),
+        not_full: Condvar::new(),
+        not_empty: Condvar::new(),
+        capacity,
+        senders: AtomicUsize::new(1),
+        receivers: AtomicUsize::new(1),
+    });
+    (
+        BoundedSender {
+            shared: shared.clone(),
+        },
+        BoundedReceiver { shared },
+    )
+}
+
+impl<T> BoundedSender<T> {
+    /// Blocks until there is room, then pushes `value`.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut queue = self.shared.queue.lock().unwrap();
+        loop {
+            if self.shared.receivers.load(Ordering::SeqCst) == 0 {
+                return Err(SendError(value));
+            }
+            if queue.len() < self.shared.capacity {
+                queue.push_back(value);
+                self.shared.not_empty.notify_one();
+                return Ok(());
+            }
+            queue = self.shared.not_full.wait(queue).unwrap();
+        }
+    }
+
+    /// Non-blocking version of [`send`](Self::send).
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        let mut queue = self.shared.queue.lock().unwrap();
+        if self.shared.receivers.load(Ordering::SeqCst) == 0 {
+            return Err(TrySendError::Disconnected(value));
+        }
+        if queue.len() == self.shared.capacity {
+            return Err(TrySendError::Full(value));
+        }
+        queue.push_back(value);
+        self.shared.not_empty.notify_one();
+        Ok(())
+    }
+}
+
+impl<T> Clone for BoundedSender<T> {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::SeqCst);
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for BoundedSender<T> {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::SeqCst) == 1 {
+            // Take the lock so a receiver that saw a live sender is already waiting.
+            drop(self.shared.queue.lock());
+            self.shared.not_empty.notify_all();
+        }
+    }
+}
+
+impl<T> BoundedReceiver<T> {
+    /// Blocks until an item is available or every sender has dropped.
+    pub fn recv(&self) -> Result<T, Disconnected> {
+        let mut queue = self.shared.queue.lock().unwrap();
+        loop {
+            if let Some(value) = queue.pop_front() {
+                self.shared.not_full.notify_one();
+                return Ok(value);
+            }
+            if self.shared.senders.load(Ordering::SeqCst) == 0 {
+                return Err(Disconnected);
+            }
+            queue = self.shared.not_empty.wait(queue).unwrap();
+        }
+    }
+}
+
+impl<T> Clone for BoundedReceiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.receivers.fetch_add(1, Ordering::SeqCst);
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for BoundedReceiver<T> {
+    fn drop(&mut self) {
+        if self.shared.receivers.fetch_sub(1, Ordering::SeqCst) == 1 {
+            // Take the lock so a sender that saw a live receiver is already waiting.
+            drop(self.shared.queue.lock());
+            self.shared.not_full.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_send_recv_fifo() {
+        let (tx, rx) = channel(2);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.recv(), Ok(2));
+    }
+
+    #[test]
+    fn test_try_send_full() {
+        let (tx, _rx) = channel(1);
+        tx.try_send(1).unwrap();
+        match tx.try_send(2) {
+            Err(TrySendError::Full(2)) => {}
+            other => panic!("expected Full(2), got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_recv_disconnected_after_senders_drop() {
+        let (tx, rx) = channel::<i32>(1);
+        drop(tx);
+        assert_eq!(rx.recv(), Err(Disconnected));
+    }
+
+    #[test]
+    fn test_send_blocks_until_room() {
+        let (tx, rx) = channel(1);
+        tx.send(1).unwrap();
+
+        let sender = thread::spawn(move || tx.send(2));
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(rx.recv(), Ok(1));
+        assert!(sender.join().unwrap().is_ok());
+        assert_eq!(rx.recv(), Ok(2));
+    }
+
+    #[test]
+    fn test_send_disconnects_once_receivers_drop() {
+        let (tx, rx) = channel(1);
+        tx.send(1).unwrap(); // fill the queue so the next send would otherwise block
+        drop(rx);
+
+        match tx.send(2) {
+            Err(SendError(2)) => {}
+            other => panic!("expected SendError(2), got {:?}", other.is_ok()),
+        }
+    }
+
+    #[test]
+    fn test_try_send_disconnected() {
+        let (tx, rx) = channel::<i32>(1);
+        drop(rx);
+        match tx.try_send(1) {
+            Err(TrySendError::Disconnected(1)) => {}
+            other => panic!("expected Disconnected(1), got {other:?}"),
+        }
+    }
+}
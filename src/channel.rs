@@ -0,0 +1,86 @@
+use std::sync::mpsc::{self, RecvError};
+use std::sync::{Arc, Mutex};
+
+/// A clone-able handle onto a single `mpsc::Receiver`, letting several
+/// threads compete for the same FIFO queue of `T`s.
+pub struct SharedReceiver<T> {
+    rx: Arc<Mutex<mpsc::Receiver<T>>>,
+}
+
+impl<T> Clone for SharedReceiver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            rx: Arc::clone(&self.rx),
+        }
+    }
+}
+
+impl<T> SharedReceiver<T> {
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let rx = self.rx.lock().unwrap();
+        rx.recv()
+    }
+}
+
+/// Like `mpsc::channel`, but the receiving half can be cloned so multiple
+/// consumer threads can drain it in parallel.
+pub fn shared_channel<T>() -> (mpsc::Sender<T>, SharedReceiver<T>) {
+    let (tx, rx) = mpsc::channel();
+    (
+        tx,
+        SharedReceiver {
+            rx: Arc::new(Mutex::new(rx)),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_recv_in_fifo_order() {
+        let (tx, rx) = shared_channel();
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.recv(), Ok(2));
+    }
+
+    #[test]
+    fn test_recv_errs_once_every_sender_drops() {
+        let (tx, rx) = shared_channel::<i32>();
+        drop(tx);
+        assert!(rx.recv().is_err());
+    }
+
+    #[test]
+    fn test_clones_share_queue_across_consumers() {
+        let (tx, rx) = shared_channel();
+        for i in 0..100 {
+            tx.send(i).unwrap();
+        }
+        drop(tx);
+
+        let consumers = (0..4)
+            .map(|_| {
+                let rx = rx.clone();
+                thread::spawn(move || {
+                    let mut received = Vec::new();
+                    while let Ok(value) = rx.recv() {
+                        received.push(value);
+                    }
+                    received
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut all = consumers
+            .into_iter()
+            .flat_map(|c| c.join().unwrap())
+            .collect::<Vec<_>>();
+        all.sort_unstable();
+        assert_eq!(all, (0..100).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod bounded;
+pub mod channel;
+pub mod matrix;
+pub mod pool;
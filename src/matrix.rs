@@ -1,10 +1,11 @@
+use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::{Add, AddAssign, Mul};
-use std::sync::mpsc;
-use std::{fmt, thread};
+use std::sync::{Arc, OnceLock};
 
 use anyhow::anyhow;
 
+use crate::pool::ThreadPool;
 use crate::vector::{dot_product, Vector};
 
 pub struct Matrix<T> {
@@ -39,6 +40,35 @@ impl<T> Matrix<T> {
             .copied()
             .collect::<Vec<_>>()
     }
+
+    pub fn transpose(&self) -> Matrix<T>
+    where
+        T: Copy,
+    {
+        let mut data = Vec::with_capacity(self.data.len());
+        for i in 0..self.col {
+            data.extend(self.col(i));
+        }
+        Matrix::new(data, self.col, self.row)
+    }
+
+    pub fn scale(&mut self, factor: T)
+    where
+        T: Copy + Mul<Output = T>,
+    {
+        for v in self.data.iter_mut() {
+            *v = *v * factor;
+        }
+    }
+
+    pub fn map<F>(&self, f: F) -> Matrix<T>
+    where
+        T: Copy,
+        F: Fn(T) -> T,
+    {
+        let data = self.data.iter().copied().map(f).collect::<Vec<_>>();
+        Matrix::new(data, self.row, self.col)
+    }
 }
 
 impl<T> Display for Matrix<T>
@@ -82,19 +112,31 @@ where
     }
 }
 
-pub struct MsgInput<T> {
-    idx: usize,
-    row: Vector<T>,
-    col: Vector<T>,
+pub fn add<T>(a: &Matrix<T>, b: &Matrix<T>) -> anyhow::Result<Matrix<T>>
+where
+    T: Copy + Add<Output = T>,
+{
+    if a.row != b.row || a.col != b.col {
+        return Err(anyhow!("Matrix add error: dimension mismatch"));
+    }
+
+    let data = a
+        .data
+        .iter()
+        .zip(b.data.iter())
+        .map(|(x, y)| *x + *y)
+        .collect::<Vec<_>>();
+    Ok(Matrix::new(data, a.row, a.col))
 }
 
-impl<T> MsgInput<T> {
-    pub fn new(idx: usize, row: Vec<T>, col: Vec<T>) -> Self {
-        Self {
-            idx,
-            row: Vector::new(row),
-            col: Vector::new(col),
-        }
+impl<T> Add for Matrix<T>
+where
+    T: Copy + Add<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        add(&self, &rhs).unwrap()
     }
 }
 
@@ -103,19 +145,13 @@ pub struct MsgOutput<T> {
     v: T,
 }
 
-pub struct Msg<T> {
-    input: MsgInput<T>,
-    // sender to send result back
-    sender: oneshot::Sender<MsgOutput<T>>,
-}
+const NUM_THREADS: usize = 4;
 
-impl<T> Msg<T> {
-    pub fn new(input: MsgInput<T>, sender: oneshot::Sender<MsgOutput<T>>) -> Self {
-        Self { input, sender }
-    }
-}
+static POOL: OnceLock<ThreadPool> = OnceLock::new();
 
-const NUM_THREADS: usize = 4;
+fn pool() -> &'static ThreadPool {
+    POOL.get_or_init(|| ThreadPool::new(NUM_THREADS))
+}
 
 pub fn multiply<T>(a: &Matrix<T>, b: &Matrix<T>) -> anyhow::Result<Matrix<T>>
 where
@@ -145,36 +181,61 @@ pub fn multiply_concurrent<T>(a: &Matrix<T>, b: &Matrix<T>) -> anyhow::Result<Ma
 where
     T: Copy + Default + Add<Output = T> + AddAssign + Mul<Output = T> + Send + 'static,
 {
-    let senders = (0..NUM_THREADS)
-        .map(|_| {
-            let (tx, rx) = mpsc::channel::<Msg<T>>();
-            thread::spawn(move || {
-                for msg in rx {
-                    let v = dot_product(msg.input.row, msg.input.col)?;
-                    if let Err(e) = msg.sender.send(MsgOutput {
-                        idx: msg.input.idx,
-                        v,
-                    }) {
+    let pool = pool();
+    let mut data = vec![T::default(); a.row * b.col];
+    let mut receivers = Vec::with_capacity(a.row * b.col);
+    for i in 0..a.row {
+        for j in 0..b.col {
+            let idx = i * b.col + j;
+            let row = Vector::new(a.row(i));
+            let col = Vector::new(b.col(j));
+            let (tx, rx) = oneshot::channel::<MsgOutput<T>>();
+            pool.submit(move || match dot_product(row, col) {
+                Ok(v) => {
+                    if let Err(e) = tx.send(MsgOutput { idx, v }) {
                         eprintln!("send error: {:?}", e)
                     }
                 }
-                Ok::<_, anyhow::Error>(())
+                Err(e) => eprintln!("dot_product error: {:?}", e),
             });
-            tx
-        })
-        .collect::<Vec<_>>();
+            receivers.push(rx);
+        }
+    }
 
+    for rx in receivers {
+        let output = rx.recv()?;
+        data[output.idx] = output.v;
+    }
+    Ok(Matrix::new(data, a.row, b.col))
+}
+
+/// Transposes `b` first so `col(j)` becomes a contiguous row slice.
+pub fn multiply_transposed<T>(a: &Matrix<T>, b: &Matrix<T>) -> anyhow::Result<Matrix<T>>
+where
+    T: Copy + Default + Add<Output = T> + AddAssign + Mul<Output = T> + Send + 'static,
+{
+    if a.col != b.row {
+        return Err(anyhow!("Matrix multiply error: a.col != b.row"));
+    }
+
+    let bt = b.transpose();
+    let pool = pool();
     let mut data = vec![T::default(); a.row * b.col];
     let mut receivers = Vec::with_capacity(a.row * b.col);
     for i in 0..a.row {
         for j in 0..b.col {
             let idx = i * b.col + j;
-            let msg_input = MsgInput::new(idx, a.row(i), b.col(j));
+            let row = Vector::new(a.row(i));
+            let col = Vector::new(bt.row(j));
             let (tx, rx) = oneshot::channel::<MsgOutput<T>>();
-            let msg = Msg::new(msg_input, tx);
-            if let Err(e) = senders[idx % NUM_THREADS].send(msg) {
-                eprintln!("error send from multiply: {:?}", e)
-            }
+            pool.submit(move || match dot_product(row, col) {
+                Ok(v) => {
+                    if let Err(e) = tx.send(MsgOutput { idx, v }) {
+                        eprintln!("send error: {:?}", e)
+                    }
+                }
+                Err(e) => eprintln!("dot_product error: {:?}", e),
+            });
             receivers.push(rx);
         }
     }
@@ -186,6 +247,76 @@ where
     Ok(Matrix::new(data, a.row, b.col))
 }
 
+pub const DEFAULT_BLOCK: usize = 32;
+
+struct TileOutput<T> {
+    bi: usize,
+    bj: usize,
+    cols: usize,
+    tile: Vec<T>,
+}
+
+/// Dispatches one task per `block x block` output tile instead of one per
+/// cell. Falls back to [`multiply`] when either matrix is smaller than one
+/// block.
+pub fn multiply_blocked<T>(a: &Matrix<T>, b: &Matrix<T>, block: usize) -> anyhow::Result<Matrix<T>>
+where
+    T: Copy + Default + Add<Output = T> + AddAssign + Mul<Output = T> + Send + Sync + 'static,
+{
+    if a.col != b.row {
+        return Err(anyhow!("Matrix multiply error: a.col != b.row"));
+    }
+    if block == 0 {
+        return Err(anyhow!("Matrix multiply error: block must be non-zero"));
+    }
+    if a.row < block || a.col < block || b.col < block {
+        return multiply(a, b);
+    }
+
+    let a_data: Arc<[T]> = Arc::from(a.data.as_slice());
+    let b_data: Arc<[T]> = Arc::from(b.data.as_slice());
+    let (a_col, b_col) = (a.col, b.col);
+
+    let pool = pool();
+    let mut data = vec![T::default(); a.row * b_col];
+    let mut receivers = Vec::new();
+    for bi in (0..a.row).step_by(block) {
+        let i_end = (bi + block).min(a.row);
+        for bj in (0..b_col).step_by(block) {
+            let j_end = (bj + block).min(b_col);
+            let a_data = a_data.clone();
+            let b_data = b_data.clone();
+            let (tx, rx) = oneshot::channel::<TileOutput<T>>();
+            pool.submit(move || {
+                let cols = j_end - bj;
+                let mut tile = vec![T::default(); (i_end - bi) * cols];
+                for i in bi..i_end {
+                    for k in 0..a_col {
+                        let a_ik = a_data[i * a_col + k];
+                        for j in bj..j_end {
+                            tile[(i - bi) * cols + (j - bj)] += a_ik * b_data[k * b_col + j];
+                        }
+                    }
+                }
+                if let Err(e) = tx.send(TileOutput { bi, bj, cols, tile }) {
+                    eprintln!("send error: {:?}", e)
+                }
+            });
+            receivers.push(rx);
+        }
+    }
+
+    for rx in receivers {
+        let output = rx.recv()?;
+        for r in 0..output.tile.len() / output.cols {
+            let dst = (output.bi + r) * b_col + output.bj;
+            let src = r * output.cols;
+            data[dst..dst + output.cols].copy_from_slice(&output.tile[src..src + output.cols]);
+        }
+    }
+    Ok(Matrix::new(data, a.row, b_col))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,4 +342,85 @@ mod tests {
         assert_eq!(format!("{:?}", c), "Matrix(row=2, col=2):\n{22 28, 49 64}");
         Ok(())
     }
+
+    #[test]
+    fn test_multiply_blocked_matches_naive() -> anyhow::Result<()> {
+        let shapes = [(2, 3, 2), (5, 4, 6), (8, 8, 8), (3, 7, 1)];
+        for (rows, inner, cols) in shapes {
+            let a_data = (0..(rows * inner) as i64).collect::<Vec<i64>>();
+            let b_data = (0..(inner * cols) as i64).collect::<Vec<i64>>();
+            let a = Matrix::new(a_data, rows, inner);
+            let b = Matrix::new(b_data, inner, cols);
+
+            let naive = multiply(&a, &b)?;
+            let blocked = multiply_blocked(&a, &b, 3)?;
+            assert_eq!(format!("{:?}", naive), format!("{:?}", blocked));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_multiply_blocked_falls_back_below_one_block() -> anyhow::Result<()> {
+        let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3);
+        let b = Matrix::new([1, 2, 3, 4, 5, 6], 3, 2);
+        let naive = multiply(&a, &b)?;
+        let blocked = multiply_blocked(&a, &b, DEFAULT_BLOCK)?;
+        assert_eq!(format!("{:?}", naive), format!("{:?}", blocked));
+        Ok(())
+    }
+
+    #[test]
+    fn test_multiply_blocked_rejects_zero_block() {
+        let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3);
+        let b = Matrix::new([1, 2, 3, 4, 5, 6], 3, 2);
+        assert!(multiply_blocked(&a, &b, 0).is_err());
+    }
+
+    #[test]
+    fn test_transpose_distributes_over_multiply() -> anyhow::Result<()> {
+        let shapes = [(2, 3, 2), (5, 4, 6), (3, 7, 1)];
+        for (rows, inner, cols) in shapes {
+            let a_data = (0..(rows * inner) as i64).collect::<Vec<i64>>();
+            let b_data = (0..(inner * cols) as i64).collect::<Vec<i64>>();
+            let a = Matrix::new(a_data, rows, inner);
+            let b = Matrix::new(b_data, inner, cols);
+
+            let lhs = multiply(&a, &b)?.transpose();
+            let rhs = multiply(&b.transpose(), &a.transpose())?;
+            assert_eq!(format!("{:?}", lhs), format!("{:?}", rhs));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_multiply_transposed_matches_naive() -> anyhow::Result<()> {
+        let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3);
+        let b = Matrix::new([1, 2, 3, 4, 5, 6], 3, 2);
+        let naive = multiply(&a, &b)?;
+        let transposed = multiply_transposed(&a, &b)?;
+        assert_eq!(format!("{:?}", naive), format!("{:?}", transposed));
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_scale_map() -> anyhow::Result<()> {
+        let a = Matrix::new([1, 2, 3, 4], 2, 2);
+        let b = Matrix::new([5, 6, 7, 8], 2, 2);
+        let sum = add(&a, &b)?;
+        assert_eq!(format!("{:?}", sum), "Matrix(row=2, col=2):\n{6 8, 10 12}");
+
+        let mut scaled = Matrix::new([1, 2, 3, 4], 2, 2);
+        scaled.scale(2);
+        assert_eq!(format!("{:?}", scaled), "Matrix(row=2, col=2):\n{2 4, 6 8}");
+
+        let doubled = Matrix::new([1, 2, 3, 4], 2, 2).map(|v| v * 2);
+        assert_eq!(
+            format!("{:?}", doubled),
+            "Matrix(row=2, col=2):\n{2 4, 6 8}"
+        );
+
+        let mismatched = Matrix::new([1, 2, 3], 1, 3);
+        assert!(add(&a, &mismatched).is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,129 @@
+use crossbeam_deque::{Injector, Steal, Stealer, Worker};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A work-stealing thread pool: workers pop from their own deque, then the
+/// injector queue, then steal from a sibling. Workers are joined on drop.
+pub struct ThreadPool {
+    injector: Arc<Injector<Job>>,
+    shutdown: Arc<AtomicBool>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    pub fn new(num_threads: usize) -> Self {
+        let injector = Arc::new(Injector::new());
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let locals: Vec<Worker<Job>> = (0..num_threads).map(|_| Worker::new_fifo()).collect();
+        let stealers: Arc<Vec<Stealer<Job>>> =
+            Arc::new(locals.iter().map(Worker::stealer).collect());
+
+        let workers = locals
+            .into_iter()
+            .enumerate()
+            .map(|(id, local)| {
+                let injector = injector.clone();
+                let stealers = stealers.clone();
+                let shutdown = shutdown.clone();
+                thread::spawn(move || Self::run(id, local, injector, stealers, shutdown))
+            })
+            .collect();
+
+        Self {
+            injector,
+            shutdown,
+            workers,
+        }
+    }
+
+    pub fn submit(&self, job: impl FnOnce() + Send + 'static) {
+        self.injector.push(Box::new(job));
+    }
+
+    fn run(
+        id: usize,
+        local: Worker<Job>,
+        injector: Arc<Injector<Job>>,
+        stealers: Arc<Vec<Stealer<Job>>>,
+        shutdown: Arc<AtomicBool>,
+    ) {
+        while !shutdown.load(Ordering::Relaxed) {
+            if let Some(job) = Self::find_job(id, &local, &injector, &stealers) {
+                job();
+            } else {
+                thread::park_timeout(Duration::from_millis(1));
+            }
+        }
+    }
+
+    fn find_job(
+        id: usize,
+        local: &Worker<Job>,
+        injector: &Injector<Job>,
+        stealers: &[Stealer<Job>],
+    ) -> Option<Job> {
+        local.pop().or_else(|| {
+            std::iter::repeat_with(|| {
+                injector.steal_batch_and_pop(local).or_else(|| {
+                    stealers
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i != id)
+                        .map(|(_, stealer)| stealer.steal())
+                        .collect()
+                })
+            })
+            .find(|steal| !steal.is_retry())
+            .and_then(Steal::success)
+        })
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        for worker in self.workers.drain(..) {
+            worker.thread().unpark();
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_submit_runs_job() {
+        let pool = ThreadPool::new(2);
+        let (tx, rx) = mpsc::channel();
+        pool.submit(move || tx.send(42).unwrap());
+        assert_eq!(rx.recv().unwrap(), 42);
+    }
+
+    #[test]
+    fn test_submit_runs_many_jobs_across_workers() {
+        let pool = ThreadPool::new(4);
+        let (tx, rx) = mpsc::channel();
+        for i in 0..100 {
+            let tx = tx.clone();
+            pool.submit(move || tx.send(i).unwrap());
+        }
+        drop(tx);
+        let mut results = rx.iter().collect::<Vec<_>>();
+        results.sort_unstable();
+        assert_eq!(results, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_drop_joins_workers() {
+        let pool = ThreadPool::new(2);
+        pool.submit(|| ());
+        drop(pool); // must not hang
+    }
+}